@@ -0,0 +1,123 @@
+//! Format dispatch: map a format name or MIME type to a `Writer`
+//!
+//! Gives CLI flags and HTTP content negotiation a single entry point: parse
+//! the requested format string, get back the right [`Writer`], call
+//! [`Writer::render`].
+
+use crate::writer::Writer;
+use crate::{GgsqlError, Result};
+
+#[cfg(feature = "vegalite")]
+use crate::writer::VegaLiteWriterBuilder;
+
+#[cfg(feature = "ggplot2")]
+use crate::writer::GgplotWriter;
+
+/// An output format ggsql knows how to write
+///
+/// New writer backends add a variant here, gated behind their own feature
+/// flag, so unsupported builds simply don't list them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[cfg(feature = "vegalite")]
+    VegaLite,
+    #[cfg(feature = "ggplot2")]
+    Ggplot2,
+}
+
+impl OutputFormat {
+    /// All formats compiled into this build
+    pub fn all() -> &'static [OutputFormat] {
+        &[
+            #[cfg(feature = "vegalite")]
+            OutputFormat::VegaLite,
+            #[cfg(feature = "ggplot2")]
+            OutputFormat::Ggplot2,
+        ]
+    }
+
+    /// Identifiers (short names and MIME types) that select this format
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "vegalite")]
+            OutputFormat::VegaLite => {
+                &["vega-lite", "vegalite", "application/vnd.vegalite.v5+json"]
+            }
+            #[cfg(feature = "ggplot2")]
+            OutputFormat::Ggplot2 => &["ggplot2", "ggplot", "text/x-r-source"],
+        }
+    }
+
+    /// Construct a writer for this format, using default settings
+    pub fn writer(&self) -> Box<dyn Writer> {
+        match self {
+            #[cfg(feature = "vegalite")]
+            OutputFormat::VegaLite => Box::new(VegaLiteWriterBuilder::new().build()),
+            #[cfg(feature = "ggplot2")]
+            OutputFormat::Ggplot2 => Box::new(GgplotWriter::new()),
+        }
+    }
+}
+
+/// Resolve a format name or MIME type (e.g. `"vega-lite"` or
+/// `"application/vnd.vegalite.v5+json"`) to a boxed [`Writer`]
+///
+/// Matching is case-insensitive.
+///
+/// # Errors
+///
+/// Returns `GgsqlError::WriterError` listing the supported formats if
+/// `format` doesn't match any known identifier.
+pub fn writer_for_format(format: &str) -> Result<Box<dyn Writer>> {
+    OutputFormat::all()
+        .iter()
+        .find(|fmt| {
+            fmt.names()
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(format))
+        })
+        .map(OutputFormat::writer)
+        .ok_or_else(|| {
+            let supported: Vec<&str> = OutputFormat::all()
+                .iter()
+                .flat_map(|fmt| fmt.names())
+                .copied()
+                .collect();
+            GgsqlError::WriterError(format!(
+                "unknown output format '{format}'; supported formats: {}",
+                supported.join(", ")
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_format_names_case_insensitively() {
+        assert!(writer_for_format("Vega-Lite").is_ok());
+        assert!(writer_for_format("VEGALITE").is_ok());
+    }
+
+    #[test]
+    fn matches_by_mime_type() {
+        assert!(writer_for_format("application/vnd.vegalite.v5+json").is_ok());
+    }
+
+    #[test]
+    fn unknown_format_lists_supported_formats_in_the_error() {
+        let err = writer_for_format("png").unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("unknown output format 'png'"));
+        for fmt in OutputFormat::all() {
+            for name in fmt.names() {
+                assert!(
+                    message.contains(name),
+                    "expected error to list '{name}': {message}"
+                );
+            }
+        }
+    }
+}
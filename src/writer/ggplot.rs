@@ -0,0 +1,249 @@
+//! R `ggplot2` code-generating writer
+//!
+//! Renders a `Plot` and its data sources as runnable R source: a `ggplot()`
+//! call with an `aes()` mapping and a `geom_*` layer, built against an
+//! inline `data.frame()` literal. This is the bridge from ggsql's
+//! SQL-flavored grammar to R's native grammar of graphics.
+
+use crate::writer::Writer;
+use crate::{DataFrame, GgsqlError, Plot, Result};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Writer that renders a `Plot` as R `ggplot2` source code
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GgplotWriter;
+
+impl GgplotWriter {
+    /// Create a new `ggplot2` writer
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn data_frame_literal(var: &str, frame: &DataFrame) -> Result<String> {
+        let records =
+            serde_json::to_value(frame).map_err(|e| GgsqlError::WriterError(e.to_string()))?;
+        let rows = records.as_array().ok_or_else(|| {
+            GgsqlError::WriterError(
+                "data source did not serialize to a row-oriented table".to_string(),
+            )
+        })?;
+
+        let mut columns: Vec<String> = Vec::new();
+        for column in frame.column_names() {
+            let cells: Vec<String> = rows
+                .iter()
+                .map(|row| r_literal(row.get(&column).unwrap_or(&serde_json::Value::Null)))
+                .collect();
+            columns.push(format!("{} = c({})", r_name(&column), cells.join(", ")));
+        }
+
+        Ok(format!(
+            "{var} <- data.frame({}, stringsAsFactors = FALSE)",
+            columns.join(", ")
+        ))
+    }
+
+    fn aes_literal(spec: &Plot) -> String {
+        let mut channels: Vec<(&String, &String)> = spec.encoding().iter().collect();
+        channels.sort_by_key(|(channel, _)| channel.as_str());
+        let mappings: Vec<String> = channels
+            .into_iter()
+            .map(|(channel, field)| format!("{} = {}", r_aes_name(channel), r_name(field)))
+            .collect();
+        format!("aes({})", mappings.join(", "))
+    }
+}
+
+/// A ggsql column or field name used as a bare R identifier: a
+/// `data.frame()` argument name, or a column reference inside `aes()`
+///
+/// ggsql column names are arbitrary SQL identifiers/aliases (`order count`,
+/// `2024sales`, `if`), but R only allows a bare symbol to stand for itself
+/// when it's syntactic: starts with a letter or `.` not followed by a
+/// digit, contains only letters/digits/`.`/`_` after that, and isn't a
+/// reserved word. Anything else must be backtick-quoted.
+fn r_name(name: &str) -> String {
+    if is_syntactic_r_name(name) {
+        name.to_string()
+    } else {
+        // Escape backslashes before backticks: backtick-quoted names follow
+        // the same escaping rules as double-quoted strings, so an
+        // unescaped `\` in the name would swallow the backtick that's
+        // meant to close the identifier (e.g. a name ending in `\` would
+        // turn the closing backtick into an escaped literal one, leaving
+        // the rest of the generated R source unterminated).
+        format!("`{}`", name.replace('\\', "\\\\").replace('`', "\\`"))
+    }
+}
+
+fn is_syntactic_r_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if !(first.is_ascii_alphabetic() || first == '.') {
+        return false;
+    }
+    if first == '.' && name.chars().nth(1).is_some_and(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    if !chars.all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_') {
+        return false;
+    }
+    !is_r_reserved_word(name)
+}
+
+fn is_r_reserved_word(name: &str) -> bool {
+    matches!(
+        name,
+        "if" | "else"
+            | "repeat"
+            | "while"
+            | "function"
+            | "for"
+            | "next"
+            | "break"
+            | "TRUE"
+            | "FALSE"
+            | "NULL"
+            | "Inf"
+            | "NaN"
+            | "NA"
+            | "NA_integer_"
+            | "NA_real_"
+            | "NA_character_"
+            | "NA_complex_"
+            | "in"
+    )
+}
+
+/// Translate a Vega-Lite-style channel name to its `aes()` argument name
+fn r_aes_name(channel: &str) -> &str {
+    match channel {
+        "color" => "colour",
+        other => other,
+    }
+}
+
+/// The `geom_*` function for a mark, or an error if there's no faithful
+/// ggplot2 translation
+fn r_geom(mark: &str) -> Result<&'static str> {
+    match mark {
+        "point" | "circle" => Ok("geom_point"),
+        "line" => Ok("geom_line"),
+        "bar" => Ok("geom_col"),
+        "area" => Ok("geom_area"),
+        "tick" => Ok("geom_rug"),
+        "boxplot" => Ok("geom_boxplot"),
+        other => Err(GgsqlError::WriterError(format!(
+            "mark '{other}' has no faithful ggplot2 translation"
+        ))),
+    }
+}
+
+/// Render a JSON cell as an R literal
+fn r_literal(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "NA".to_string(),
+        serde_json::Value::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => format!("{s:?}"),
+        other => format!("{:?}", other.to_string()),
+    }
+}
+
+impl Writer for GgplotWriter {
+    fn write(&self, spec: &Plot, data: &HashMap<String, DataFrame>) -> Result<String> {
+        self.validate(spec)?;
+        let frame = crate::writer::resolve_data_source(data, spec.data_source())?;
+
+        let var = "df";
+        let mut out = String::new();
+        writeln!(out, "library(ggplot2)").ok();
+        writeln!(out, "{}", Self::data_frame_literal(var, frame)?).ok();
+        writeln!(out).ok();
+        write!(
+            out,
+            "ggplot({var}, {}) +\n  {}()",
+            Self::aes_literal(spec),
+            r_geom(spec.mark())?
+        )
+        .ok();
+        if let Some(title) = spec.title() {
+            write!(out, " +\n  ggtitle({title:?})").ok();
+        }
+        writeln!(out).ok();
+        Ok(out)
+    }
+
+    fn validate(&self, spec: &Plot) -> Result<()> {
+        r_geom(spec.mark())?;
+        if spec.encoding().is_empty() {
+            return Err(GgsqlError::WriterError(
+                "plot has no encoded channels".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoding(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn write_errors_on_mark_with_no_ggplot2_translation() {
+        let writer = GgplotWriter::new();
+        let spec = Plot::new("arc", "cars", encoding(&[("x", "mpg")]));
+        let mut data = HashMap::new();
+        data.insert(
+            "cars".to_string(),
+            DataFrame::from_rows(vec!["mpg"], vec![vec![serde_json::json!(21.0)]]),
+        );
+
+        let err = writer.write(&spec, &data).unwrap_err();
+
+        assert!(err.to_string().contains("arc"));
+    }
+
+    #[test]
+    fn non_syntactic_column_and_field_names_are_backtick_quoted() {
+        let writer = GgplotWriter::new();
+        let spec = Plot::new("point", "cars", encoding(&[("x", "order count")]));
+        let mut data = HashMap::new();
+        data.insert(
+            "cars".to_string(),
+            DataFrame::from_rows(vec!["order count"], vec![vec![serde_json::json!(3)]]),
+        );
+
+        let r_source = writer.write(&spec, &data).unwrap();
+
+        assert!(r_source.contains("`order count` = c(3)"));
+        assert!(r_source.contains("x = `order count`"));
+    }
+
+    #[test]
+    fn syntactic_names_are_left_unquoted() {
+        assert_eq!(r_name("mpg"), "mpg");
+        assert_eq!(r_name("order count"), "`order count`");
+        assert_eq!(r_name("2024sales"), "`2024sales`");
+        assert_eq!(r_name("if"), "`if`");
+    }
+
+    #[test]
+    fn backslashes_are_escaped_before_the_closing_backtick() {
+        // Escaping the backtick alone would turn `foo\` into `foo\`` — R
+        // reads `\`` as an escaped literal backtick, not a closing
+        // delimiter, leaving the identifier (and the rest of the R source)
+        // unterminated.
+        assert_eq!(r_name("foo\\"), "`foo\\\\`");
+    }
+}
@@ -0,0 +1,144 @@
+//! Gzip compression decorator for any [`Writer`]
+//!
+//! Wraps another writer's streaming output in a gzip encoder, producing
+//! compressed bytes suitable for HTTP responses with
+//! `Content-Encoding: gzip` or on-disk `.json.gz` artifacts. Vega-Lite specs
+//! with inlined data are highly compressible, so this pairs naturally with
+//! the streaming `write_to`/`render_to` path: the inner writer's JSON flows
+//! into the gzip encoder, which writes compressed bytes into the caller's
+//! sink.
+//!
+//! `GzipWriter` doesn't implement `Writer` itself — its output is binary,
+//! which doesn't fit `Writer::write`'s `String` return type — so it exposes
+//! its own `write_to`/`render_to` pair instead.
+
+use crate::api::Prepared;
+use crate::writer::Writer;
+use crate::{DataFrame, GgsqlError, Plot, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::HashMap;
+use std::io;
+
+/// Gzip-compresses the streamed output of an inner [`Writer`]
+#[derive(Debug, Clone)]
+pub struct GzipWriter<W> {
+    inner: W,
+    level: Compression,
+}
+
+impl<W: Writer> GzipWriter<W> {
+    /// Wrap `inner`, compressing at flate2's default level
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            level: Compression::default(),
+        }
+    }
+
+    /// Wrap `inner`, compressing at the given level (0 = none, 9 = best)
+    pub fn with_level(inner: W, level: u32) -> Self {
+        Self {
+            inner,
+            level: Compression::new(level),
+        }
+    }
+
+    /// Render a prepared visualization as gzip-compressed bytes into `out`
+    ///
+    /// Returns `out` once the gzip stream is finished, so the caller
+    /// decides whether and when to flush it.
+    pub fn render_to<Out: io::Write>(&self, prepared: &Prepared, out: Out) -> Result<Out> {
+        self.write_to(prepared.plot(), prepared.data_map(), out)
+    }
+
+    /// Generate output from a spec and data sources as gzip-compressed
+    /// bytes into `out`
+    ///
+    /// Returns `out` once the gzip stream is finished, so the caller
+    /// decides whether and when to flush it. On error, `out` is dropped:
+    /// `GzEncoder`'s `Drop` impl best-effort-flushes and writes a valid
+    /// gzip trailer regardless of whether the inner writer succeeded, so a
+    /// caller who reused `out` across the error wouldn't be able to tell a
+    /// truncated stream from a complete one. Don't write `out` anywhere on
+    /// error.
+    pub fn write_to<Out: io::Write>(
+        &self,
+        spec: &Plot,
+        data: &HashMap<String, DataFrame>,
+        out: Out,
+    ) -> Result<Out> {
+        let mut encoder = GzEncoder::new(out, self.level);
+        if let Err(e) = self.inner.write_to(spec, data, &mut encoder) {
+            // Abandon the stream here rather than falling through to
+            // `encoder.finish()` or letting `encoder` drop implicitly:
+            // either way flate2 writes a complete, validly-terminated gzip
+            // trailer to `out`, which would make a failed render look like
+            // a successful (if truncated) one downstream.
+            drop(encoder.into_inner());
+            return Err(e);
+        }
+        encoder
+            .finish()
+            .map_err(|e| GgsqlError::WriterError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    struct OkWriter;
+
+    impl Writer for OkWriter {
+        fn write(&self, spec: &Plot, _data: &HashMap<String, DataFrame>) -> Result<String> {
+            Ok(spec.mark().to_string())
+        }
+
+        fn validate(&self, _spec: &Plot) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct FailingWriter;
+
+    impl Writer for FailingWriter {
+        fn write(&self, _spec: &Plot, _data: &HashMap<String, DataFrame>) -> Result<String> {
+            Err(GgsqlError::WriterError("boom".to_string()))
+        }
+
+        fn validate(&self, _spec: &Plot) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn round_trips_back_to_the_inner_writers_output() {
+        let writer = GzipWriter::new(OkWriter);
+        let spec = Plot::new("point", "cars", HashMap::new());
+        let data = HashMap::new();
+
+        let compressed = writer.write_to(&spec, &data, Vec::new()).unwrap();
+
+        let mut decompressed = String::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+
+        assert_eq!(decompressed, OkWriter.write(&spec, &data).unwrap());
+    }
+
+    #[test]
+    fn error_from_inner_writer_never_hands_back_a_completed_stream() {
+        // `out` isn't returned to the caller on error, so there's no
+        // spuriously-valid gzip trailer for a caller to mistake for success.
+        let writer = GzipWriter::new(FailingWriter);
+        let spec = Plot::new("point", "cars", HashMap::new());
+        let data = HashMap::new();
+
+        let err = writer.write_to(&spec, &data, Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("boom"));
+    }
+}
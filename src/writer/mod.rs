@@ -6,29 +6,49 @@
 //! # Architecture
 //!
 //! All writers implement the `Writer` trait, which provides:
-//! - Prepared → Output conversion via `render()`
-//! - Low-level Plot + Data → Output via `write()`
+//! - Prepared → Output conversion via `render()`, or streamed via `render_to()`
+//! - Low-level Plot + Data → Output via `write()`, or streamed via `write_to()`
 //! - Format-specific rendering logic
 //!
 //! # Example
 //!
 //! ```rust,ignore
-//! use ggsql::writer::{Writer, VegaLiteWriter};
+//! use ggsql::writer::{Writer, VegaLiteWriterBuilder};
 //!
-//! let writer = VegaLiteWriter::new();
+//! let writer = VegaLiteWriterBuilder::new().build();
 //! let json = writer.render(&prepared)?;
 //! println!("{}", json);
 //! ```
 
 use crate::api::Prepared;
-use crate::{DataFrame, Plot, Result};
+use crate::{DataFrame, GgsqlError, Plot, Result};
 use std::collections::HashMap;
+use std::io;
 
 #[cfg(feature = "vegalite")]
 pub mod vegalite;
 
 #[cfg(feature = "vegalite")]
-pub use vegalite::VegaLiteWriter;
+pub use vegalite::{DataMode, VegaLiteWriter, VegaLiteWriterBuilder};
+
+#[cfg(feature = "vegalite-typed")]
+pub use vegalite::VegaLiteSpec;
+
+#[cfg(feature = "ggplot2")]
+pub mod ggplot;
+
+#[cfg(feature = "ggplot2")]
+pub use ggplot::GgplotWriter;
+
+#[cfg(feature = "gzip")]
+pub mod gzip;
+
+#[cfg(feature = "gzip")]
+pub use gzip::GzipWriter;
+
+pub mod format;
+
+pub use format::{writer_for_format, OutputFormat};
 
 /// Trait for visualization output writers
 ///
@@ -55,6 +75,27 @@ pub trait Writer {
         self.write(prepared.plot(), prepared.data_map())
     }
 
+    /// Render a prepared visualization directly into a writer
+    ///
+    /// Like `render`, but streams output into `out` instead of materializing
+    /// it as a `String` first. Prefer this when writing to a file, socket, or
+    /// any sink where holding the full output in memory isn't necessary.
+    ///
+    /// Flushing is the caller's responsibility: this method does not flush
+    /// `out`, so callers embedding the writer in an HTTP handler or piping
+    /// through a compressor stay in control of when bytes hit the wire.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GgsqlError::WriterError` if rendering fails, or an I/O error
+    /// wrapped in `GgsqlError::WriterError` if writing to `out` fails.
+    fn render_to<W: io::Write>(&self, prepared: &Prepared, out: W) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.write_to(prepared.plot(), prepared.data_map(), out)
+    }
+
     /// Generate output from a visualization specification and data sources
     ///
     /// This is a lower-level method that takes the plot and data separately.
@@ -78,6 +119,34 @@ pub trait Writer {
     /// - Output generation fails
     fn write(&self, spec: &Plot, data: &HashMap<String, DataFrame>) -> Result<String>;
 
+    /// Generate output from a spec and data sources directly into a writer
+    ///
+    /// Like `write`, but streams output into `out` instead of returning a
+    /// `String`. The default implementation falls back to `write` and copies
+    /// the result into `out`; writers that can serialize incrementally
+    /// (e.g. `VegaLiteWriter`, via `serde_json::to_writer`) should override
+    /// this to avoid building the full output in memory first.
+    ///
+    /// Flushing `out` is left to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GgsqlError::WriterError` if rendering fails, or an I/O error
+    /// wrapped in `GgsqlError::WriterError` if writing to `out` fails.
+    fn write_to<W: io::Write>(
+        &self,
+        spec: &Plot,
+        data: &HashMap<String, DataFrame>,
+        mut out: W,
+    ) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let rendered = self.write(spec, data)?;
+        out.write_all(rendered.as_bytes())
+            .map_err(|e| GgsqlError::WriterError(e.to_string()))
+    }
+
     /// Validate that a spec is compatible with this writer
     ///
     /// Checks whether the spec can be rendered by this writer without
@@ -92,3 +161,78 @@ pub trait Writer {
     /// Ok(()) if the spec is compatible, otherwise an error
     fn validate(&self, spec: &Plot) -> Result<()>;
 }
+
+/// Delegate to the boxed writer, so a `Box<dyn Writer>` (e.g. from
+/// [`writer_for_format`]) is itself `Sized` and gets the default
+/// `render_to`/`write_to` impls that `Self: Sized` excludes from the
+/// `dyn Writer` vtable — without this, a caller holding a boxed writer from
+/// the format registry could never stream its output or wrap it in
+/// `GzipWriter`, both of which require a `Sized` `Writer`.
+impl<T: Writer + ?Sized> Writer for Box<T> {
+    fn render(&self, prepared: &Prepared) -> Result<String> {
+        (**self).render(prepared)
+    }
+
+    fn write(&self, spec: &Plot, data: &HashMap<String, DataFrame>) -> Result<String> {
+        (**self).write(spec, data)
+    }
+
+    fn validate(&self, spec: &Plot) -> Result<()> {
+        (**self).validate(spec)
+    }
+}
+
+/// Look up `name` in `data`, or return the standard "no such data source"
+/// error every writer surfaces for a missing source
+///
+/// Shared so `GgplotWriter`, `VegaLiteWriter`, and `VegaLiteSpec::from_plot`
+/// don't each carry their own copy of this error message.
+pub(crate) fn resolve_data_source<'a>(
+    data: &'a HashMap<String, DataFrame>,
+    name: &str,
+) -> Result<&'a DataFrame> {
+    data.get(name).ok_or_else(|| {
+        GgsqlError::WriterError(format!("no data source named '{name}' was supplied"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_data_source_errors_with_name_when_missing() {
+        let data: HashMap<String, DataFrame> = HashMap::new();
+        let err = resolve_data_source(&data, "cars").unwrap_err();
+        assert!(err.to_string().contains("cars"));
+    }
+
+    /// A writer that only implements the required `write`/`validate`
+    /// methods, to exercise the trait's default `write_to` fallback.
+    struct EchoWriter;
+
+    impl Writer for EchoWriter {
+        fn write(&self, spec: &Plot, _data: &HashMap<String, DataFrame>) -> Result<String> {
+            Ok(spec.mark().to_string())
+        }
+
+        fn validate(&self, _spec: &Plot) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn boxed_writer_gets_the_default_streaming_methods() {
+        // `Box<dyn Writer>` is what `writer_for_format` returns; the
+        // `impl Writer for Box<T>` above is what makes `write_to` (and not
+        // just `write`) callable on it.
+        let boxed: Box<dyn Writer> = Box::new(EchoWriter);
+        let spec = Plot::new("point", "cars", HashMap::new());
+        let data = HashMap::new();
+
+        let mut buf = Vec::new();
+        boxed.write_to(&spec, &data, &mut buf).unwrap();
+
+        assert_eq!(buf, b"point");
+    }
+}
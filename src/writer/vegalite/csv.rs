@@ -0,0 +1,95 @@
+//! Minimal RFC 4180-style CSV encoding for inline `DataFrame` embedding
+
+use crate::{DataFrame, GgsqlError, Result};
+
+/// Encode a `DataFrame` as CSV text: a header row from the frame's schema,
+/// followed by one row per record, with fields quoted only when necessary.
+pub(super) fn encode(frame: &DataFrame) -> Result<String> {
+    let records =
+        serde_json::to_value(frame).map_err(|e| GgsqlError::WriterError(e.to_string()))?;
+    let rows = records.as_array().ok_or_else(|| {
+        GgsqlError::WriterError("data source did not serialize to a row-oriented table".to_string())
+    })?;
+    let columns = frame.column_names();
+
+    let mut out = String::new();
+    out.push_str(&join_row(columns.iter().map(String::as_str)));
+    out.push_str("\r\n");
+    for row in rows {
+        let cells = columns
+            .iter()
+            .map(|column| cell_text(row.get(column).unwrap_or(&serde_json::Value::Null)));
+        out.push_str(&join_row(cells));
+        out.push_str("\r\n");
+    }
+    Ok(out)
+}
+
+fn join_row<'a>(fields: impl Iterator<Item = impl AsRef<str> + 'a>) -> String {
+    fields
+        .map(|field| quote_if_needed(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::Bool(b) => b.to_string(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn quote_if_needed(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_a_field_containing_a_comma() {
+        let frame = DataFrame::from_rows(
+            vec!["city", "population"],
+            vec![vec![
+                serde_json::json!("Springfield, IL"),
+                serde_json::json!(114230),
+            ]],
+        );
+
+        let csv = encode(&frame).unwrap();
+
+        assert_eq!(csv, "city,population\r\n\"Springfield, IL\",114230\r\n");
+    }
+
+    #[test]
+    fn escapes_embedded_quotes() {
+        let frame = DataFrame::from_rows(vec!["quip"], vec![vec![serde_json::json!("say \"hi\"")]]);
+
+        let csv = encode(&frame).unwrap();
+
+        assert_eq!(csv, "quip\r\n\"say \"\"hi\"\"\"\r\n");
+    }
+
+    #[test]
+    fn rows_use_crlf_line_endings_and_one_cell_per_column() {
+        let frame = DataFrame::from_rows(
+            vec!["a", "b"],
+            vec![
+                vec![serde_json::json!(1), serde_json::json!(2)],
+                vec![serde_json::json!(3), serde_json::json!(4)],
+            ],
+        );
+
+        let csv = encode(&frame).unwrap();
+
+        assert_eq!(csv, "a,b\r\n1,2\r\n3,4\r\n");
+    }
+}
@@ -0,0 +1,409 @@
+//! Vega-Lite output writer
+//!
+//! Renders a `Plot` and its associated data sources as a Vega-Lite v5 JSON
+//! specification, the grammar consumed by vega-lite.js and the tooling built
+//! on top of it (the Vega editor, vega-embed, etc.).
+
+use crate::writer::Writer;
+use crate::{DataFrame, GgsqlError, Plot, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io;
+
+#[cfg(feature = "vegalite-typed")]
+pub mod spec;
+
+#[cfg(feature = "vegalite-typed")]
+pub use spec::VegaLiteSpec;
+
+mod csv;
+
+const DEFAULT_SCHEMA_VERSION: &str = "v5";
+const DEFAULT_INDENT_WIDTH: usize = 2;
+
+fn schema_url(version: &str) -> String {
+    format!("https://vega.github.io/schema/vega-lite/{version}.json")
+}
+
+/// How [`VegaLiteWriter`] embeds a `Plot`'s data sources in the emitted spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataMode {
+    /// Inline each row as a JSON record under `data.values` (the default)
+    InlineJson,
+    /// Inline data as CSV text under `data.values`, with
+    /// `data.format = {"type": "csv"}` — more compact than per-record JSON
+    /// for wide numeric tables
+    InlineCsv,
+    /// Leave data sources as external `data.url` references for the host to
+    /// resolve, keyed by data source name
+    Url(HashMap<String, String>),
+}
+
+impl Default for DataMode {
+    fn default() -> Self {
+        Self::InlineJson
+    }
+}
+
+/// Builder for [`VegaLiteWriter`]
+///
+/// Mirrors the configuration-object pattern used elsewhere in ggsql: set the
+/// knobs you care about, then call [`build`](Self::build) for an immutable
+/// writer.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use ggsql::writer::VegaLiteWriterBuilder;
+///
+/// let writer = VegaLiteWriterBuilder::new()
+///     .pretty(false)
+///     .schema_version("v6")
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct VegaLiteWriterBuilder {
+    pretty: bool,
+    indent_width: usize,
+    schema_version: String,
+    include_schema: bool,
+    config: Option<serde_json::Value>,
+    data_mode: DataMode,
+}
+
+impl Default for VegaLiteWriterBuilder {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            indent_width: DEFAULT_INDENT_WIDTH,
+            schema_version: DEFAULT_SCHEMA_VERSION.to_string(),
+            include_schema: true,
+            config: None,
+            data_mode: DataMode::default(),
+        }
+    }
+}
+
+impl VegaLiteWriterBuilder {
+    /// Start a new builder with ggsql's default output settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit human-readable, indented JSON (the default) vs. compact JSON
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Number of spaces per indentation level when `pretty` is enabled
+    pub fn indent_width(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+
+    /// Target Vega-Lite schema version, e.g. `"v5"` or `"v5.17"`
+    pub fn schema_version(mut self, version: impl Into<String>) -> Self {
+        self.schema_version = version.into();
+        self
+    }
+
+    /// Whether to emit the `$schema` field (default `true`)
+    ///
+    /// Disable this when embedding the spec somewhere that already pins a
+    /// schema version out of band.
+    pub fn include_schema(mut self, include: bool) -> Self {
+        self.include_schema = include;
+        self
+    }
+
+    /// A theme/config block included verbatim as the spec's top-level
+    /// `config`
+    ///
+    /// `Plot` has no per-plot config of its own to merge against, so this
+    /// is the only source of the emitted `config` block.
+    pub fn config(mut self, config: serde_json::Value) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// How to embed data sources in the emitted spec (default
+    /// [`DataMode::InlineJson`])
+    pub fn data_mode(mut self, mode: DataMode) -> Self {
+        self.data_mode = mode;
+        self
+    }
+
+    /// Build the immutable writer
+    pub fn build(self) -> VegaLiteWriter {
+        VegaLiteWriter {
+            pretty: self.pretty,
+            indent_width: self.indent_width,
+            schema_url: schema_url(&self.schema_version),
+            include_schema: self.include_schema,
+            config: self.config,
+            data_mode: self.data_mode,
+        }
+    }
+}
+
+/// Writer that renders a `Plot` as a Vega-Lite JSON specification
+///
+/// Construct one with [`VegaLiteWriterBuilder`]; once built, a `VegaLiteWriter`
+/// is immutable.
+#[derive(Debug, Clone)]
+pub struct VegaLiteWriter {
+    pretty: bool,
+    indent_width: usize,
+    schema_url: String,
+    include_schema: bool,
+    config: Option<serde_json::Value>,
+    data_mode: DataMode,
+}
+
+impl Default for VegaLiteWriter {
+    fn default() -> Self {
+        VegaLiteWriterBuilder::new().build()
+    }
+}
+
+impl VegaLiteWriter {
+    /// Build the typed Vega-Lite spec for a plot and its data sources
+    ///
+    /// Exposed publicly so programmatic callers can tweak the chart (e.g.
+    /// override `config` or add a channel) before handing it back through
+    /// [`VegaLiteSpec::to_value`] or their own serialization.
+    #[cfg(feature = "vegalite-typed")]
+    pub fn to_spec(&self, spec: &Plot, data: &HashMap<String, DataFrame>) -> Result<VegaLiteSpec> {
+        let mut typed = VegaLiteSpec::from_plot(spec, data, &self.data_mode)?;
+        if self.include_schema {
+            typed.schema = Some(self.schema_url.clone());
+        }
+        if let Some(config) = &self.config {
+            typed.config = Some(config.clone());
+        }
+        Ok(typed)
+    }
+
+    /// Build the `data` block of the spec according to `self.data_mode`
+    #[cfg(not(feature = "vegalite-typed"))]
+    fn data_block(
+        &self,
+        spec: &Plot,
+        data: &HashMap<String, DataFrame>,
+    ) -> Result<serde_json::Value> {
+        match &self.data_mode {
+            DataMode::Url(urls) => {
+                let url = urls.get(spec.data_source()).ok_or_else(|| {
+                    GgsqlError::WriterError(format!(
+                        "no URL configured for data source '{}'",
+                        spec.data_source()
+                    ))
+                })?;
+                Ok(serde_json::json!({ "url": url }))
+            }
+            DataMode::InlineJson => {
+                let frame = crate::writer::resolve_data_source(data, spec.data_source())?;
+                let values = serde_json::to_value(frame)
+                    .map_err(|e| GgsqlError::WriterError(e.to_string()))?;
+                Ok(serde_json::json!({ "values": values }))
+            }
+            DataMode::InlineCsv => {
+                let frame = crate::writer::resolve_data_source(data, spec.data_source())?;
+                let values = csv::encode(frame)?;
+                Ok(serde_json::json!({ "values": values, "format": { "type": "csv" } }))
+            }
+        }
+    }
+
+    /// Assemble the Vega-Lite spec as a `serde_json::Value`
+    ///
+    /// Shared by `write` and `write_to` so both the buffered and streaming
+    /// paths produce identical output.
+    fn build_spec(
+        &self,
+        spec: &Plot,
+        data: &HashMap<String, DataFrame>,
+    ) -> Result<serde_json::Value> {
+        #[cfg(feature = "vegalite-typed")]
+        {
+            self.to_spec(spec, data)?.to_value()
+        }
+        #[cfg(not(feature = "vegalite-typed"))]
+        {
+            let data_block = self.data_block(spec, data)?;
+
+            let mut encoding = serde_json::Map::new();
+            for (channel, field) in spec.encoding() {
+                encoding.insert(channel.clone(), serde_json::json!({ "field": field }));
+            }
+
+            let mut json = serde_json::json!({
+                "data": data_block,
+                "mark": spec.mark(),
+                "encoding": encoding,
+            });
+            let obj = json.as_object_mut().expect("spec is always a JSON object");
+            if self.include_schema {
+                obj.insert("$schema".to_string(), serde_json::json!(self.schema_url));
+            }
+            if let Some(config) = &self.config {
+                obj.insert("config".to_string(), config.clone());
+            }
+            if let Some(title) = spec.title() {
+                obj.insert("title".to_string(), serde_json::json!(title));
+            }
+            if let Some(width) = spec.width() {
+                obj.insert("width".to_string(), serde_json::json!(width));
+            }
+            if let Some(height) = spec.height() {
+                obj.insert("height".to_string(), serde_json::json!(height));
+            }
+            Ok(json)
+        }
+    }
+
+    fn serialize_to<W: io::Write>(&self, value: &serde_json::Value, out: W) -> Result<()> {
+        if self.pretty {
+            let indent = " ".repeat(self.indent_width);
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            let mut serializer = serde_json::Serializer::with_formatter(out, formatter);
+            value
+                .serialize(&mut serializer)
+                .map_err(|e| GgsqlError::WriterError(e.to_string()))
+        } else {
+            serde_json::to_writer(out, value).map_err(|e| GgsqlError::WriterError(e.to_string()))
+        }
+    }
+}
+
+impl Writer for VegaLiteWriter {
+    fn write(&self, spec: &Plot, data: &HashMap<String, DataFrame>) -> Result<String> {
+        self.validate(spec)?;
+        let value = self.build_spec(spec, data)?;
+        let mut buf = Vec::new();
+        self.serialize_to(&value, &mut buf)?;
+        String::from_utf8(buf).map_err(|e| GgsqlError::WriterError(e.to_string()))
+    }
+
+    fn write_to<W: io::Write>(
+        &self,
+        spec: &Plot,
+        data: &HashMap<String, DataFrame>,
+        out: W,
+    ) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.validate(spec)?;
+        let value = self.build_spec(spec, data)?;
+        self.serialize_to(&value, out)
+    }
+
+    fn validate(&self, spec: &Plot) -> Result<()> {
+        // `Writer::validate` doesn't have access to resolved data sources,
+        // so it can only check the spec in isolation. With the
+        // `vegalite-typed` feature, `to_spec`/`VegaLiteSpec::from_plot`
+        // additionally catches an encoding channel referencing a field
+        // absent from the data, at construction time rather than at render
+        // time.
+        if spec.encoding().is_empty() {
+            return Err(GgsqlError::WriterError(
+                "plot has no encoded channels".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cars() -> HashMap<String, DataFrame> {
+        let mut data = HashMap::new();
+        data.insert(
+            "cars".to_string(),
+            DataFrame::from_rows(
+                vec!["mpg", "hp"],
+                vec![vec![serde_json::json!(21.0), serde_json::json!(110)]],
+            ),
+        );
+        data
+    }
+
+    fn scatter() -> Plot {
+        let mut encoding = HashMap::new();
+        encoding.insert("x".to_string(), "mpg".to_string());
+        encoding.insert("y".to_string(), "hp".to_string());
+        Plot::new("point", "cars", encoding)
+    }
+
+    #[test]
+    fn write_rejects_plot_with_no_encoded_channels() {
+        let writer = VegaLiteWriter::default();
+        let spec = Plot::new("point", "cars", HashMap::new());
+
+        let err = writer.write(&spec, &cars()).unwrap_err();
+
+        assert!(err.to_string().contains("no encoded channels"));
+    }
+
+    #[test]
+    fn write_to_streams_the_same_bytes_as_write() {
+        let writer = VegaLiteWriter::default();
+        let spec = scatter();
+        let data = cars();
+
+        let buffered = writer.write(&spec, &data).unwrap();
+
+        let mut streamed = Vec::new();
+        writer.write_to(&spec, &data, &mut streamed).unwrap();
+
+        assert_eq!(buffered.into_bytes(), streamed);
+    }
+
+    #[test]
+    fn compact_output_omits_indentation() {
+        let writer = VegaLiteWriterBuilder::new().pretty(false).build();
+        let json = writer.write(&scatter(), &cars()).unwrap();
+
+        assert!(!json.contains('\n'));
+    }
+
+    #[test]
+    fn schema_version_changes_the_emitted_schema_url() {
+        let writer = VegaLiteWriterBuilder::new().schema_version("v6").build();
+        let json = writer.write(&scatter(), &cars()).unwrap();
+
+        assert!(json.contains("https://vega.github.io/schema/vega-lite/v6.json"));
+    }
+
+    #[test]
+    fn include_schema_false_omits_the_schema_field() {
+        let writer = VegaLiteWriterBuilder::new().include_schema(false).build();
+        let json = writer.write(&scatter(), &cars()).unwrap();
+
+        assert!(!json.contains("$schema"));
+    }
+
+    #[test]
+    fn indent_width_changes_the_indentation() {
+        let writer = VegaLiteWriterBuilder::new().indent_width(4).build();
+        let json = writer.write(&scatter(), &cars()).unwrap();
+
+        assert!(json.lines().any(|line| line.starts_with("    \"")));
+        assert!(!json.lines().any(|line| line.starts_with("  \"")));
+    }
+
+    #[test]
+    fn config_is_included_verbatim_in_the_rendered_spec() {
+        let writer = VegaLiteWriterBuilder::new()
+            .config(serde_json::json!({ "background": "#fff" }))
+            .build();
+        let json = writer.write(&scatter(), &cars()).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["config"]["background"], "#fff");
+    }
+}
@@ -0,0 +1,182 @@
+//! Typed Vega-Lite specification model
+//!
+//! An Altair-style typed alternative to assembling `serde_json::Value`s by
+//! hand: the top-level spec, its `data` block and each encoding channel are
+//! Rust structs, and a channel referencing a column absent from the data is
+//! caught in [`VegaLiteSpec::from_plot`] before anything is serialized. The
+//! mark and the channel names are plain strings taken from the `Plot`, so
+//! they are not checked at compile time.
+
+use super::{csv, DataMode};
+use crate::{DataFrame, GgsqlError, Plot, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A typed, serializable Vega-Lite v5 top-level specification
+#[derive(Debug, Clone, Serialize)]
+pub struct VegaLiteSpec {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub data: DataSpec,
+    pub mark: String,
+    pub encoding: HashMap<String, EncodingChannel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
+/// The `data` block of a Vega-Lite spec, one variant per [`DataMode`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum DataSpec {
+    /// Rows inlined as JSON records
+    Values { values: serde_json::Value },
+    /// Rows inlined as CSV text, with an explicit `format`
+    Csv { values: String, format: CsvFormat },
+    /// An external data source the host resolves by URL
+    Url { url: String },
+}
+
+/// The `data.format` block that tells Vega-Lite how to parse inline `values`
+#[derive(Debug, Clone, Serialize)]
+pub struct CsvFormat {
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// A single encoding channel, e.g. `x`, `y`, `color`
+#[derive(Debug, Clone, Serialize)]
+pub struct EncodingChannel {
+    pub field: String,
+}
+
+impl VegaLiteSpec {
+    /// Build a typed spec from a `Plot`, its data sources, and the data
+    /// embedding mode to use
+    ///
+    /// Unlike hand-built JSON, this walks the plot's encoding up front and
+    /// returns `GgsqlError::WriterError` if a channel references a field
+    /// that doesn't exist in the resolved `DataFrame` (when one is
+    /// available — a `DataMode::Url` source may not be), rather than
+    /// emitting an invalid spec that only fails once Vega-Lite tries to
+    /// render it.
+    pub fn from_plot(
+        spec: &Plot,
+        data: &HashMap<String, DataFrame>,
+        mode: &DataMode,
+    ) -> Result<Self> {
+        let frame = data.get(spec.data_source());
+
+        if let Some(frame) = frame {
+            let columns = frame.column_names();
+            for (channel, field) in spec.encoding() {
+                if !columns.iter().any(|c| c == field) {
+                    return Err(GgsqlError::WriterError(format!(
+                        "encoding channel '{channel}' references field '{field}', which is not a column of data source '{}'",
+                        spec.data_source()
+                    )));
+                }
+            }
+        }
+
+        let mut encoding = HashMap::new();
+        for (channel, field) in spec.encoding() {
+            encoding.insert(
+                channel.clone(),
+                EncodingChannel {
+                    field: field.clone(),
+                },
+            );
+        }
+
+        let data = match mode {
+            DataMode::Url(urls) => {
+                let url = urls.get(spec.data_source()).ok_or_else(|| {
+                    GgsqlError::WriterError(format!(
+                        "no URL configured for data source '{}'",
+                        spec.data_source()
+                    ))
+                })?;
+                DataSpec::Url { url: url.clone() }
+            }
+            DataMode::InlineJson => {
+                let frame = crate::writer::resolve_data_source(data, spec.data_source())?;
+                let values = serde_json::to_value(frame)
+                    .map_err(|e| GgsqlError::WriterError(e.to_string()))?;
+                DataSpec::Values { values }
+            }
+            DataMode::InlineCsv => {
+                let frame = crate::writer::resolve_data_source(data, spec.data_source())?;
+                DataSpec::Csv {
+                    values: csv::encode(frame)?,
+                    format: CsvFormat {
+                        kind: "csv".to_string(),
+                    },
+                }
+            }
+        };
+
+        Ok(Self {
+            schema: None,
+            data,
+            mark: spec.mark().to_string(),
+            encoding,
+            title: spec.title().map(str::to_string),
+            width: spec.width(),
+            height: spec.height(),
+            config: None,
+        })
+    }
+
+    /// Serialize this spec to a `serde_json::Value`
+    pub fn to_value(&self) -> Result<serde_json::Value> {
+        serde_json::to_value(self).map_err(|e| GgsqlError::WriterError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_when_a_channel_references_a_field_missing_from_the_data() {
+        let mut encoding = HashMap::new();
+        encoding.insert("x".to_string(), "mpg".to_string());
+        encoding.insert("y".to_string(), "horsepower".to_string());
+        let spec = Plot::new("point", "cars", encoding);
+
+        let mut data = HashMap::new();
+        data.insert(
+            "cars".to_string(),
+            DataFrame::from_rows(vec!["mpg", "hp"], vec![vec![serde_json::json!(21.0)]]),
+        );
+
+        let err = VegaLiteSpec::from_plot(&spec, &data, &DataMode::InlineJson).unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("horsepower"));
+        assert!(message.contains("cars"));
+    }
+
+    #[test]
+    fn builds_a_spec_when_every_channel_matches_a_column() {
+        let mut encoding = HashMap::new();
+        encoding.insert("x".to_string(), "mpg".to_string());
+        let spec = Plot::new("point", "cars", encoding);
+
+        let mut data = HashMap::new();
+        data.insert(
+            "cars".to_string(),
+            DataFrame::from_rows(vec!["mpg"], vec![vec![serde_json::json!(21.0)]]),
+        );
+
+        let built = VegaLiteSpec::from_plot(&spec, &data, &DataMode::InlineJson).unwrap();
+
+        assert_eq!(built.encoding["x"].field, "mpg");
+    }
+}